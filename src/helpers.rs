@@ -3,10 +3,14 @@ use std::ffi::CString;
 use bytes::{BytesMut, BufMut};
 use rand::Rng;
 use sha1_smol::Sha1;
+use crate::ParseError;
 
 /// A SHA1 hash value.
 pub type Sha1HashValue = [u8; 20];
 
+/// The length in bytes of a machine ID message object.
+const MESSAGE_LENGTH: usize = 155;
+
 /// Creates a machine id from the given SHA-1 hash values.
 pub fn create_machine_id_from_values(
     value_bb3: &Sha1HashValue,
@@ -38,6 +42,110 @@ pub fn create_machine_id_from_values(
     buffer.into()
 }
 
+/// Parses the three SHA-1 hash values out of a machine ID message object.
+pub fn parse_machine_id_from_message(
+    bytes: &[u8],
+) -> Result<(Sha1HashValue, Sha1HashValue, Sha1HashValue), ParseError> {
+    if bytes.len() != MESSAGE_LENGTH {
+        return Err(ParseError::InvalidLength(bytes.len()));
+    }
+
+    if bytes[0] != 0 {
+        return Err(ParseError::InvalidHeader);
+    }
+
+    let (header, mut offset) = read_c_string(bytes, 1, "MessageObject header")?;
+
+    if header != "MessageObject" {
+        return Err(ParseError::InvalidHeader);
+    }
+
+    let value_bb3;
+    (value_bb3, offset) = read_field(bytes, offset, "BB3")?;
+
+    let value_ff2;
+    (value_ff2, offset) = read_field(bytes, offset, "FF2")?;
+
+    let value_3b3;
+    (value_3b3, offset) = read_field(bytes, offset, "3B3")?;
+
+    if bytes[offset] != 8 || bytes[offset + 1] != 8 {
+        return Err(ParseError::InvalidTerminator);
+    }
+
+    Ok((value_bb3, value_ff2, value_3b3))
+}
+
+/// Reads a `1` type byte followed by a null-terminated key name and a null-terminated hex string,
+/// returning the decoded hash value and the offset immediately after it.
+fn read_field(
+    bytes: &[u8],
+    offset: usize,
+    key: &str,
+) -> Result<(Sha1HashValue, usize), ParseError> {
+    if bytes[offset] != 1 {
+        return Err(ParseError::InvalidTypeByte(key.to_string()));
+    }
+
+    let offset = expect_c_string(bytes, offset + 1, key, &format!("{key} key name"))?;
+    let (hex_value, offset) = read_c_string(bytes, offset, &format!("{key} value"))?;
+    let value = hex_string_to_bytes(&hex_value)?;
+
+    Ok((value, offset))
+}
+
+/// Reads a null-terminated string starting at `offset`, returning it along with the offset
+/// immediately after the null terminator. `label` identifies what was being read, for error
+/// reporting.
+fn read_c_string(bytes: &[u8], offset: usize, label: &str) -> Result<(String, usize), ParseError> {
+    let nul_index = bytes[offset..]
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or_else(|| ParseError::UnterminatedField(label.to_string()))?;
+    let value = std::str::from_utf8(&bytes[offset..offset + nul_index])
+        .map_err(|_| ParseError::UnterminatedField(label.to_string()))?
+        .to_string();
+
+    Ok((value, offset + nul_index + 1))
+}
+
+/// Reads a null-terminated string starting at `offset` and checks that it matches `expected`,
+/// returning the offset immediately after the null terminator. `label` identifies what was being
+/// read, for error reporting.
+fn expect_c_string(
+    bytes: &[u8],
+    offset: usize,
+    expected: &str,
+    label: &str,
+) -> Result<usize, ParseError> {
+    let (value, offset) = read_c_string(bytes, offset, label)?;
+
+    if value != expected {
+        return Err(ParseError::InvalidFieldName(value));
+    }
+
+    Ok(offset)
+}
+
+/// Decodes a 40-character hex string into a SHA1 hash value.
+pub fn hex_string_to_bytes(input: &str) -> Result<Sha1HashValue, ParseError> {
+    if input.len() != 40 {
+        return Err(ParseError::InvalidHex(input.to_string()));
+    }
+
+    let mut value = [0u8; 20];
+
+    for (i, byte) in value.iter_mut().enumerate() {
+        let hex_byte = input.get(i * 2..i * 2 + 2)
+            .ok_or_else(|| ParseError::InvalidHex(input.to_string()))?;
+
+        *byte = u8::from_str_radix(hex_byte, 16)
+            .map_err(|_| ParseError::InvalidHex(input.to_string()))?;
+    }
+
+    Ok(value)
+}
+
 /// Converts a byte slice to a hex string.
 pub fn bytes_to_hex_string(input: &[u8]) -> String {
     input
@@ -64,6 +172,27 @@ pub fn get_custom_hash_value(value: &str) -> Sha1HashValue {
     create_sha1(value.as_bytes())
 }
 
+/// Gets a SHA1 hash value for the given `key` and `machine_guid`.
+pub fn get_machine_guid_hash_value(key: &str, machine_guid: &str) -> Sha1HashValue {
+    create_sha1(format!("SteamUser Hash {key} {machine_guid}").as_bytes())
+}
+
+/// Searches for a random SHA1 hash value whose hex representation starts with the given
+/// case-insensitive `prefix`, giving up after `max_attempts` attempts.
+pub fn find_hash_value_with_prefix(prefix: &str, max_attempts: usize) -> Option<Sha1HashValue> {
+    let prefix = prefix.to_uppercase();
+
+    for _ in 0..max_attempts {
+        let value = get_random_hash_value();
+
+        if bytes_to_hex_string(&value).starts_with(&prefix) {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
 /// Gets a null-terminated (C string) byte vec from the given string.
 pub fn get_c_string(input: &str) -> Vec<u8> {
     // As long as no null bytes ("\0") are in the string, this will never panic.
@@ -108,7 +237,14 @@ mod tests {
     #[test]
     fn tests_get_account_name_hash_value() {
         let hash_value = get_account_name_hash_value("test", "test");
-        
+
+        assert_eq!(hash_value.len(), 20);
+    }
+
+    #[test]
+    fn tests_get_machine_guid_hash_value() {
+        let hash_value = get_machine_guid_hash_value("test", "test");
+
         assert_eq!(hash_value.len(), 20);
     }
     
@@ -129,8 +265,86 @@ mod tests {
     #[test]
     fn tests_get_c_string_bytes() {
         let bytes = get_c_string("test");
-        
+
         assert_eq!(bytes.as_slice().len(), 5);
         assert_eq!([116, 101, 115, 116, 0], bytes.as_slice());
     }
+
+    #[test]
+    fn tests_hex_string_to_bytes() {
+        let hex_string = "0001020304050607".to_string() + "08090A0B0C0D0E0F" + "10111213";
+        let bytes = hex_string_to_bytes(&hex_string).unwrap();
+
+        assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+    }
+
+    #[test]
+    fn tests_hex_string_to_bytes_invalid_length() {
+        assert!(hex_string_to_bytes("too short").is_err());
+    }
+
+    #[test]
+    fn tests_parse_machine_id_from_message() {
+        let value_bb3 = get_random_hash_value();
+        let value_ff2 = get_random_hash_value();
+        let value_3b3 = get_random_hash_value();
+        let message = create_machine_id_from_values(&value_bb3, &value_ff2, &value_3b3);
+        let (parsed_bb3, parsed_ff2, parsed_3b3) = parse_machine_id_from_message(&message).unwrap();
+
+        assert_eq!(parsed_bb3, value_bb3);
+        assert_eq!(parsed_ff2, value_ff2);
+        assert_eq!(parsed_3b3, value_3b3);
+    }
+
+    #[test]
+    fn tests_parse_machine_id_from_message_invalid_length() {
+        assert!(parse_machine_id_from_message(&[0; 10]).is_err());
+    }
+
+    #[test]
+    fn tests_parse_machine_id_from_message_invalid_type_byte() {
+        let value_bb3 = get_random_hash_value();
+        let value_ff2 = get_random_hash_value();
+        let value_3b3 = get_random_hash_value();
+        let mut message = create_machine_id_from_values(&value_bb3, &value_ff2, &value_3b3);
+        // Corrupt the BB3 field's type byte.
+        message[15] = 0;
+
+        assert_eq!(
+            parse_machine_id_from_message(&message),
+            Err(ParseError::InvalidTypeByte("BB3".to_string())),
+        );
+    }
+
+    #[test]
+    fn tests_parse_machine_id_from_message_unterminated_value() {
+        let value_bb3 = get_random_hash_value();
+        let value_ff2 = get_random_hash_value();
+        let value_3b3 = get_random_hash_value();
+        let mut message = create_machine_id_from_values(&value_bb3, &value_ff2, &value_3b3);
+        // Corrupt the 3B3 field's hex value by removing its null terminator. Since nothing but
+        // the non-zero terminator bytes follow it, the missing null runs off the end of the
+        // message rather than being masked by a later field's null byte.
+        message[152] = b'0';
+
+        assert_eq!(
+            parse_machine_id_from_message(&message),
+            Err(ParseError::UnterminatedField("3B3 value".to_string())),
+        );
+    }
+
+    #[test]
+    fn tests_find_hash_value_with_prefix() {
+        let value = find_hash_value_with_prefix("", 1).unwrap();
+
+        assert_eq!(value.len(), 20);
+    }
+
+    #[test]
+    fn tests_find_hash_value_with_prefix_gives_up() {
+        // This prefix is long enough that it won't be found within a single attempt.
+        let value = find_hash_value_with_prefix("0000000000000000000000000000000000000A", 1);
+
+        assert_eq!(value, None);
+    }
 }
\ No newline at end of file
@@ -28,8 +28,16 @@
 //!     machine_id: machine_id.into(),
 //! };
 //! ```
+//!
+//! # Feature flags
+//!
+//! - `serde` - Implements `Serialize` and `Deserialize` for [`MachineID`], using the same hex
+//!   strings produced by its `Display` impl.
+//! - `uuid` - Adds [`MachineID::from_uuid`], for deriving a machine ID from a `uuid::Uuid`.
 
 mod helpers;
+#[cfg(feature = "serde")]
+mod serde_support;
 
 use std::fmt;
 use helpers::Sha1HashValue;
@@ -74,7 +82,36 @@ impl MachineID {
     pub fn from_account_name(account_name: &str) -> Self {
         Self::new(MachineIDType::AccountName(account_name))
     }
-    
+
+    /// Creates a machine ID from the given machine GUID. This produces a machine ID that is
+    /// stable across logins on the same machine, matching how the real Steam client persists its
+    /// machine ID, rather than deriving it from the account name or generating it at random.
+    ///
+    /// # Examples
+    /// ```
+    /// use steam_machine_id::MachineID;
+    ///
+    /// let machine_id = MachineID::from_machine_guid("d48d3889-5b08-4782-8a9c-8c784e2da6d9");
+    /// ```
+    pub fn from_machine_guid(machine_guid: &str) -> Self {
+        Self::new(MachineIDType::MachineGuid(machine_guid))
+    }
+
+    /// Creates a machine ID from the given [`uuid::Uuid`], using its canonical hyphenated string
+    /// form as the machine GUID. Requires the `uuid` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use steam_machine_id::MachineID;
+    ///
+    /// let machine_guid = uuid::Uuid::new_v4();
+    /// let machine_id = MachineID::from_uuid(machine_guid);
+    /// ```
+    #[cfg(feature = "uuid")]
+    pub fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self::from_machine_guid(&uuid.to_string())
+    }
+
     /// Creates a machine ID using a custom format for specific use-cases. These could be anything 
     /// you want but should generally follow the format below.
     /// 
@@ -101,6 +138,49 @@ impl MachineID {
         ))
     }
     
+    /// Creates a random machine ID whose [`MachineIDField`] hash value starts with the given
+    /// case-insensitive hex `prefix`. This brute-forces random hash values until one matches, so
+    /// longer prefixes take exponentially longer to find - each added hex nibble multiplies the
+    /// expected number of attempts by 16.
+    ///
+    /// # Examples
+    /// ```
+    /// use steam_machine_id::{MachineID, MachineIDField};
+    ///
+    /// let machine_id = MachineID::random_with_prefix("f", MachineIDField::Bb3);
+    /// ```
+    pub fn random_with_prefix(prefix: &str, field: MachineIDField) -> Self {
+        Self::try_random_with_prefix(prefix, field, usize::MAX)
+            .expect("a hash value with the given prefix should eventually be found")
+    }
+
+    /// Attempts to create a random machine ID whose [`MachineIDField`] hash value starts with the
+    /// given case-insensitive hex `prefix`, giving up and returning `None` after `max_attempts`
+    /// attempts.
+    ///
+    /// # Examples
+    /// ```
+    /// use steam_machine_id::{MachineID, MachineIDField};
+    ///
+    /// let machine_id = MachineID::try_random_with_prefix("f", MachineIDField::Bb3, 1_000_000);
+    /// ```
+    pub fn try_random_with_prefix(
+        prefix: &str,
+        field: MachineIDField,
+        max_attempts: usize,
+    ) -> Option<Self> {
+        let value = helpers::find_hash_value_with_prefix(prefix, max_attempts)?;
+        let mut machine_id = Self::random();
+
+        match field {
+            MachineIDField::Bb3 => machine_id.value_bb3 = value,
+            MachineIDField::Ff2 => machine_id.value_ff2 = value,
+            MachineIDField::ThreeB3 => machine_id.value_3b3 = value,
+        }
+
+        Some(machine_id)
+    }
+
     /// Creates a message object from the machine ID.
     pub fn to_message(&self) -> Vec<u8> {
         helpers::create_machine_id_from_values(
@@ -109,8 +189,77 @@ impl MachineID {
             &self.value_3b3,
         )
     }
+
+    /// Parses a machine ID from a message object, as produced by [`MachineID::to_message`].
+    ///
+    /// # Examples
+    /// ```
+    /// use steam_machine_id::MachineID;
+    ///
+    /// let machine_id = MachineID::random();
+    /// let message = machine_id.to_message();
+    /// let parsed = MachineID::from_message(&message).unwrap();
+    ///
+    /// assert_eq!(machine_id, parsed);
+    /// ```
+    pub fn from_message(message: &[u8]) -> Result<Self, ParseError> {
+        let (value_bb3, value_ff2, value_3b3) = helpers::parse_machine_id_from_message(message)?;
+
+        Ok(Self {
+            value_bb3,
+            value_ff2,
+            value_3b3,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for MachineID {
+    type Error = ParseError;
+
+    fn try_from(message: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_message(message)
+    }
+}
+
+/// An error parsing a [`MachineID`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The message was not the expected length of 155 bytes.
+    InvalidLength(usize),
+    /// The message did not begin with the expected `MessageObject` header.
+    InvalidHeader,
+    /// A field did not have the expected key name.
+    InvalidFieldName(String),
+    /// A field's type byte was not the expected `1`.
+    InvalidTypeByte(String),
+    /// A null-terminated string for the named field ran off the end of the message, or was not
+    /// valid UTF-8.
+    UnterminatedField(String),
+    /// A hash value was not a valid 40-character hex string.
+    InvalidHex(String),
+    /// The message did not end with the expected terminator bytes.
+    InvalidTerminator,
+    /// The string did not match the `BB3.<hex>:FF2.<hex>:3B3.<hex>` display format.
+    InvalidFormat(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(f, "expected a message of 155 bytes, got {len}"),
+            Self::InvalidHeader => write!(f, "message did not contain the expected header"),
+            Self::InvalidFieldName(name) => write!(f, "unexpected field name `{name}`"),
+            Self::InvalidTypeByte(field) => write!(f, "`{field}` field did not have the expected type byte"),
+            Self::UnterminatedField(field) => write!(f, "`{field}` was missing its null terminator or was not valid UTF-8"),
+            Self::InvalidHex(value) => write!(f, "`{value}` is not a valid hash value"),
+            Self::InvalidTerminator => write!(f, "message did not end with the expected terminator bytes"),
+            Self::InvalidFormat(value) => write!(f, "`{value}` is not a valid machine ID string"),
+        }
+    }
 }
 
+impl std::error::Error for ParseError {}
+
 impl From<MachineID> for Vec<u8> {
     fn from(machine_id: MachineID) -> Self {
         machine_id.to_message()
@@ -135,6 +284,53 @@ impl fmt::Display for MachineID {
     }
 }
 
+/// Parses a [`MachineID`] from its `BB3.<hex>:FF2.<hex>:3B3.<hex>` display form.
+///
+/// # Examples
+/// ```
+/// use steam_machine_id::MachineID;
+///
+/// let machine_id = MachineID::from_account_name("accountname");
+/// let parsed: MachineID = machine_id.to_string().parse().unwrap();
+///
+/// assert_eq!(machine_id, parsed);
+/// ```
+impl std::str::FromStr for MachineID {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split(':');
+        let value_bb3 = parse_display_component(components.next(), "BB3", s)?;
+        let value_ff2 = parse_display_component(components.next(), "FF2", s)?;
+        let value_3b3 = parse_display_component(components.next(), "3B3", s)?;
+
+        if components.next().is_some() {
+            return Err(ParseError::InvalidFormat(s.to_string()));
+        }
+
+        Ok(Self {
+            value_bb3,
+            value_ff2,
+            value_3b3,
+        })
+    }
+}
+
+/// Parses a single `KEY.<hex>` component out of the `Display` form of a [`MachineID`].
+fn parse_display_component(
+    component: Option<&str>,
+    key: &str,
+    s: &str,
+) -> Result<Sha1HashValue, ParseError> {
+    let component = component.ok_or_else(|| ParseError::InvalidFormat(s.to_string()))?;
+    let hex_value = component
+        .strip_prefix(key)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .ok_or_else(|| ParseError::InvalidFieldName(component.to_string()))?;
+
+    helpers::hex_string_to_bytes(hex_value)
+}
+
 impl From<MachineIDType<'_>> for MachineID {
     fn from(machine_id_type: MachineIDType<'_>) -> Self {
         match machine_id_type {
@@ -152,6 +348,13 @@ impl From<MachineIDType<'_>> for MachineID {
                     value_3b3: helpers::get_account_name_hash_value("3B3", account_name),
                 }
             },
+            MachineIDType::MachineGuid(machine_guid) => {
+                MachineID {
+                    value_bb3: helpers::get_machine_guid_hash_value("BB3", machine_guid),
+                    value_ff2: helpers::get_machine_guid_hash_value("FF2", machine_guid),
+                    value_3b3: helpers::get_machine_guid_hash_value("3B3", machine_guid),
+                }
+            },
             MachineIDType::CustomFormat(value_bb3, value_ff2, value_3b3) => {
                 MachineID {
                     value_bb3: helpers::get_custom_hash_value(value_bb3),
@@ -163,6 +366,18 @@ impl From<MachineIDType<'_>> for MachineID {
     }
 }
 
+/// Identifies which hash value of a [`MachineID`] a vanity prefix should be matched against, for
+/// use with [`MachineID::random_with_prefix`] and [`MachineID::try_random_with_prefix`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MachineIDField {
+    /// The `BB3` hash value.
+    Bb3,
+    /// The `FF2` hash value.
+    Ff2,
+    /// The `3B3` hash value.
+    ThreeB3,
+}
+
 /// Options for creating a Steam machine ID.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum MachineIDType<'a> {
@@ -170,6 +385,8 @@ enum MachineIDType<'a> {
     Random,
     /// A machine ID created from the given account name.
     AccountName(&'a str),
+    /// A machine ID created from the given machine GUID.
+    MachineGuid(&'a str),
     /// A machine ID created using a custom format.
     CustomFormat(&'a str, &'a str, &'a str),
 }
@@ -213,6 +430,98 @@ mod tests {
         assert_eq!(machine_id[154], 8);
     }
     
+    #[test]
+    fn tests_machine_id_round_trip() {
+        let machine_id = MachineID::from_account_name("accountname");
+        let message = machine_id.to_message();
+        let parsed = MachineID::from_message(&message).unwrap();
+
+        assert_eq!(machine_id, parsed);
+
+        let parsed: MachineID = message.as_slice().try_into().unwrap();
+
+        assert_eq!(machine_id, parsed);
+    }
+
+    #[test]
+    fn tests_machine_id_from_message_invalid_length() {
+        assert!(MachineID::from_message(&[0; 10]).is_err());
+    }
+
+    #[test]
+    fn tests_machine_id_display_round_trip() {
+        let machine_id = MachineID::from_account_name("accountname");
+        let parsed: MachineID = machine_id.to_string().parse().unwrap();
+
+        assert_eq!(machine_id, parsed);
+    }
+
+    #[test]
+    fn tests_machine_id_from_str_invalid_format() {
+        assert!("not a machine id".parse::<MachineID>().is_err());
+    }
+
+    #[test]
+    fn tests_create_machine_id_from_machine_guid() {
+        let machine_id = MachineID::from_machine_guid("d48d3889-5b08-4782-8a9c-8c784e2da6d9").to_message();
+
+        assert_eq!(machine_id.len(), 155);
+        assert_eq!(machine_id[0], 0);
+        assert_eq!(&machine_id[1..15], get_c_string("MessageObject").as_slice());
+        assert_eq!(machine_id[15], 1);
+        assert_eq!(&machine_id[16..20], get_c_string("BB3").as_slice());
+        assert_eq!(machine_id[61], 1);
+        assert_eq!(&machine_id[62..66], get_c_string("FF2").as_slice());
+        assert_eq!(machine_id[107], 1);
+        assert_eq!(&machine_id[108..112], get_c_string("3B3").as_slice());
+        assert_eq!(machine_id[153], 8);
+        assert_eq!(machine_id[154], 8);
+    }
+
+    #[test]
+    fn tests_machine_id_from_machine_guid_is_deterministic() {
+        let machine_id_a = MachineID::from_machine_guid("d48d3889-5b08-4782-8a9c-8c784e2da6d9");
+        let machine_id_b = MachineID::from_machine_guid("d48d3889-5b08-4782-8a9c-8c784e2da6d9");
+
+        assert_eq!(machine_id_a, machine_id_b);
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn tests_machine_id_from_uuid_matches_machine_guid() {
+        let uuid = uuid::Uuid::new_v4();
+        let machine_id_from_uuid = MachineID::from_uuid(uuid);
+        let machine_id_from_guid = MachineID::from_machine_guid(&uuid.to_string());
+
+        assert_eq!(machine_id_from_uuid, machine_id_from_guid);
+    }
+
+    #[test]
+    fn tests_random_with_prefix() {
+        let machine_id = MachineID::random_with_prefix("", MachineIDField::Bb3);
+
+        assert_eq!(bytes_to_hex_string(&machine_id.value_bb3).len(), 40);
+    }
+
+    #[test]
+    fn tests_try_random_with_prefix_matches_field() {
+        let machine_id = MachineID::try_random_with_prefix("F", MachineIDField::Ff2, 1_000_000)
+            .unwrap();
+
+        assert!(bytes_to_hex_string(&machine_id.value_ff2).starts_with('F'));
+    }
+
+    #[test]
+    fn tests_try_random_with_prefix_gives_up() {
+        let machine_id = MachineID::try_random_with_prefix(
+            "0000000000000000000000000000000000000A",
+            MachineIDField::ThreeB3,
+            1,
+        );
+
+        assert_eq!(machine_id, None);
+    }
+
     #[test]
     fn tests_machine_id() {
         let machine_id = MachineID::from_account_name("accountname");
@@ -0,0 +1,66 @@
+//! `serde` support for [`MachineID`], gated behind the `serde` feature.
+//!
+//! A machine ID is serialized as its three hash values encoded as the same 40-character hex
+//! strings produced by [`helpers::bytes_to_hex_string`], so that the serialized form matches the
+//! components rendered by [`MachineID`]'s `Display` impl.
+
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{helpers, MachineID};
+
+/// The hex-encoded representation of a [`MachineID`] used for serialization.
+#[derive(Serialize, Deserialize)]
+struct MachineIDHex {
+    value_bb3: String,
+    value_ff2: String,
+    value_3b3: String,
+}
+
+impl Serialize for MachineID {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        MachineIDHex {
+            value_bb3: helpers::bytes_to_hex_string(&self.value_bb3),
+            value_ff2: helpers::bytes_to_hex_string(&self.value_ff2),
+            value_3b3: helpers::bytes_to_hex_string(&self.value_3b3),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MachineID {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex = MachineIDHex::deserialize(deserializer)?;
+
+        Ok(MachineID {
+            value_bb3: helpers::hex_string_to_bytes(&hex.value_bb3).map_err(DeError::custom)?,
+            value_ff2: helpers::hex_string_to_bytes(&hex.value_ff2).map_err(DeError::custom)?,
+            value_3b3: helpers::hex_string_to_bytes(&hex.value_3b3).map_err(DeError::custom)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_machine_id_serde_round_trip() {
+        let machine_id = MachineID::from_account_name("accountname");
+        let json = serde_json::to_string(&machine_id).unwrap();
+        let parsed: MachineID = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(machine_id, parsed);
+    }
+
+    #[test]
+    fn tests_machine_id_serde_invalid_hex() {
+        let json = r#"{"value_bb3":"not hex","value_ff2":"00","value_3b3":"00"}"#;
+
+        assert!(serde_json::from_str::<MachineID>(json).is_err());
+    }
+}